@@ -0,0 +1,6 @@
+mod snowflake;
+
+mod ratelimit;
+
+#[cfg(feature = "webhook")]
+pub mod webhook;
@@ -0,0 +1,233 @@
+use reqwest::{header::HeaderMap, RequestBuilder, Response, StatusCode};
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+use tracing::{debug, trace};
+
+/// The bucket key used for routes that don't return per-route `X-RateLimit-*`
+/// headers, so they still get throttled instead of firing unbounded.
+const GLOBAL_BUCKET: &str = "global";
+
+/// How many times a request is retried after a `429` before giving up and
+/// handing the response back to the caller, so a sustained outage doesn't
+/// retry forever.
+const MAX_RETRIES: u32 = 5;
+
+/// The tracked state of a single rate limit bucket, built from the
+/// `X-RateLimit-Remaining`/`X-RateLimit-Reset` headers top.gg sends back on
+/// each response. `X-RateLimit-Reset` is an absolute Unix-epoch-seconds
+/// timestamp, not a delta.
+#[derive(Debug, Clone, Copy, Default)]
+struct Bucket {
+  remaining: u32,
+  reset: Option<Instant>,
+}
+
+impl Bucket {
+  fn update(&mut self, headers: &HeaderMap) {
+    if let Some(remaining) = header_u32(headers, "x-ratelimit-remaining") {
+      self.remaining = remaining;
+    }
+
+    if let Some(reset_epoch) = header_u32(headers, "x-ratelimit-reset") {
+      self.reset = reset_instant(u64::from(reset_epoch));
+    }
+  }
+
+  /// Returns how long the caller should wait before this bucket allows another request.
+  fn wait_duration(&self) -> Option<Duration> {
+    if self.remaining > 0 {
+      return None;
+    }
+
+    self.reset.and_then(|reset| reset.checked_duration_since(Instant::now()))
+  }
+}
+
+/// Converts an absolute `X-RateLimit-Reset` Unix-epoch-seconds timestamp into
+/// a monotonic [`Instant`], or `None` if it has already passed.
+fn reset_instant(epoch_secs: u64) -> Option<Instant> {
+  let reset_at = UNIX_EPOCH + Duration::from_secs(epoch_secs);
+  let remaining = reset_at.duration_since(SystemTime::now()).ok()?;
+
+  Some(Instant::now() + remaining)
+}
+
+fn header_u32(headers: &HeaderMap, name: &str) -> Option<u32> {
+  headers.get(name)?.to_str().ok()?.parse().ok()
+}
+
+/// A [`reqwest`]-backed request layer that coordinates outgoing requests
+/// against top.gg's rate limits, so bursts of calls back off automatically
+/// instead of getting `429`'d.
+///
+/// Buckets are tracked per-route from the `X-RateLimit-*` response headers.
+/// Routes that don't return those headers fall back to a single global
+/// bucket shared between them.
+#[must_use]
+pub(crate) struct LimitedRequester {
+  client: reqwest::Client,
+  buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl LimitedRequester {
+  pub(crate) fn new(client: reqwest::Client) -> Self {
+    Self {
+      client,
+      buckets: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// Sends a request against `route`, waiting out the route's bucket first
+  /// and retrying on a `429` (as directed by `Retry-After`) up to
+  /// [`MAX_RETRIES`] times before handing the last response back as-is.
+  pub(crate) async fn request<F>(&self, route: &str, build: F) -> reqwest::Result<Response>
+  where
+    F: Fn(&reqwest::Client) -> RequestBuilder,
+  {
+    for attempt in 0..=MAX_RETRIES {
+      self.reserve_slot(route).await;
+
+      let response = build(&self.client).send().await?;
+      self.update_bucket(route, response.headers()).await;
+
+      if response.status() == StatusCode::TOO_MANY_REQUESTS {
+        if attempt == MAX_RETRIES {
+          debug!(route, attempt, "giving up after repeated 429s from this route");
+          return Ok(response);
+        }
+
+        let retry_after = header_u32(response.headers(), "retry-after")
+          .map_or(Duration::from_secs(1), |secs| Duration::from_secs(u64::from(secs)));
+
+        debug!(route, ?retry_after, "rate limit bucket exhausted, retrying after backoff");
+        tokio::time::sleep(retry_after).await;
+        continue;
+      }
+
+      return Ok(response);
+    }
+
+    unreachable!("loop always returns within MAX_RETRIES + 1 iterations")
+  }
+
+  /// Waits out the route's bucket (falling back to the global one), then
+  /// optimistically reserves a slot by decrementing `remaining` before
+  /// releasing the lock, so concurrent callers for the same route don't all
+  /// observe `remaining > 0` and fire at once.
+  async fn reserve_slot(&self, route: &str) {
+    loop {
+      let wait = {
+        let mut buckets = self.buckets.lock().await;
+        let key = if buckets.contains_key(route) { route } else { GLOBAL_BUCKET };
+        let bucket = buckets.entry(key.to_owned()).or_default();
+
+        match bucket.wait_duration() {
+          Some(wait) => Some(wait),
+          None => {
+            bucket.remaining = bucket.remaining.saturating_sub(1);
+            None
+          }
+        }
+      };
+
+      let Some(wait) = wait else {
+        return;
+      };
+
+      trace!(route, ?wait, "throttling request until rate limit bucket resets");
+      tokio::time::sleep(wait).await;
+    }
+  }
+
+  async fn update_bucket(&self, route: &str, headers: &HeaderMap) {
+    let key = if headers.contains_key("x-ratelimit-remaining") {
+      route
+    } else {
+      GLOBAL_BUCKET
+    };
+
+    self
+      .buckets
+      .lock()
+      .await
+      .entry(key.to_owned())
+      .or_default()
+      .update(headers);
+  }
+}
+
+impl std::fmt::Debug for LimitedRequester {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("LimitedRequester").finish_non_exhaustive()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{header_u32, Bucket};
+  use reqwest::header::{HeaderMap, HeaderValue};
+  use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+  fn headers(pairs: &[(&str, &str)]) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+
+    for (key, value) in pairs {
+      headers.insert(*key, HeaderValue::from_str(value).unwrap());
+    }
+
+    headers
+  }
+
+  fn epoch_secs_from_now(offset: Duration, in_future: bool) -> u64 {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+
+    if in_future {
+      (now + offset).as_secs()
+    } else {
+      now.saturating_sub(offset).as_secs()
+    }
+  }
+
+  #[test]
+  fn waits_until_a_future_absolute_reset() {
+    let mut bucket = Bucket::default();
+    let reset_epoch = epoch_secs_from_now(Duration::from_secs(30), true);
+
+    bucket.update(&headers(&[
+      ("x-ratelimit-remaining", "0"),
+      ("x-ratelimit-reset", &reset_epoch.to_string()),
+    ]));
+
+    let wait = bucket.wait_duration().expect("bucket should report a wait");
+    assert!(wait <= Duration::from_secs(31), "{wait:?} should not hang for decades");
+  }
+
+  #[test]
+  fn does_not_wait_once_the_reset_has_passed() {
+    let mut bucket = Bucket::default();
+    let reset_epoch = epoch_secs_from_now(Duration::from_secs(30), false);
+
+    bucket.update(&headers(&[
+      ("x-ratelimit-remaining", "0"),
+      ("x-ratelimit-reset", &reset_epoch.to_string()),
+    ]));
+
+    assert_eq!(bucket.wait_duration(), None);
+  }
+
+  #[test]
+  fn does_not_wait_while_remaining_requests_are_left() {
+    let mut bucket = Bucket::default();
+    bucket.update(&headers(&[("x-ratelimit-remaining", "5")]));
+
+    assert_eq!(bucket.wait_duration(), None);
+  }
+
+  #[test]
+  fn header_u32_ignores_malformed_values() {
+    let headers = headers(&[("x-ratelimit-remaining", "not-a-number")]);
+
+    assert_eq!(header_u32(&headers, "x-ratelimit-remaining"), None);
+  }
+}
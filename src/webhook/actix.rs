@@ -0,0 +1,43 @@
+use super::vote::IncomingVote;
+use actix_web::{dev::Payload, error::ErrorBadRequest, web::Bytes, Error, FromRequest, HttpRequest};
+use serde::de::DeserializeOwned;
+use std::future::Future;
+use std::pin::Pin;
+use tracing::{trace, Instrument};
+
+impl<Q> FromRequest for IncomingVote<Q>
+where
+  Q: DeserializeOwned + Default + 'static,
+{
+  type Error = Error;
+  type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+  #[cfg_attr(docsrs, doc(cfg(feature = "actix")))]
+  fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+    let authorization = req
+      .headers()
+      .get("authorization")
+      .and_then(|value| value.to_str().ok())
+      .unwrap_or_default()
+      .to_owned();
+
+    let body = Bytes::from_request(req, payload);
+    let span = tracing::info_span!("webhook");
+
+    Box::pin(
+      async move {
+        let body = body.await?;
+        let event = serde_json::from_slice(&body).map_err(|error| {
+          trace!(body = %String::from_utf8_lossy(&body), %error, "failed to deserialize webhook body");
+          ErrorBadRequest(error)
+        })?;
+
+        Ok(Self {
+          authorization,
+          event,
+        })
+      }
+      .instrument(span),
+    )
+  }
+}
@@ -0,0 +1,97 @@
+use super::vote::{VoteBroadcaster, VoteHandler, WebhookEvent, WebhookState};
+use axum::{
+  body::Bytes,
+  extract::State,
+  http::{HeaderMap, StatusCode},
+  routing::post,
+  Router,
+};
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+use tracing::{debug, trace, warn};
+
+/// Builds an [`axum::Router`] that authenticates incoming [Top.gg](https://top.gg)
+/// webhook requests and dispatches each one to `handler`'s [`VoteHandler::voted`].
+#[must_use]
+#[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
+pub fn webhook<T, Q>(password: impl Into<String>, handler: T) -> Router
+where
+  T: VoteHandler<Q>,
+  Q: DeserializeOwned + Default + Clone + Send + Sync + 'static,
+{
+  build_router(password, handler, None)
+}
+
+/// Like [`webhook`], but also publishes every authenticated vote to `broadcaster`'s
+/// subscribers, in addition to calling `handler`'s [`VoteHandler::voted`].
+#[must_use]
+#[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
+pub fn webhook_with_broadcaster<T, Q>(
+  password: impl Into<String>,
+  handler: T,
+  broadcaster: VoteBroadcaster<Q>,
+) -> Router
+where
+  T: VoteHandler<Q>,
+  Q: DeserializeOwned + Default + Clone + Send + Sync + 'static,
+{
+  build_router(password, handler, Some(broadcaster))
+}
+
+fn build_router<T, Q>(password: impl Into<String>, handler: T, broadcaster: Option<VoteBroadcaster<Q>>) -> Router
+where
+  T: VoteHandler<Q>,
+  Q: DeserializeOwned + Default + Clone + Send + Sync + 'static,
+{
+  let state = Arc::new(WebhookState {
+    state: handler,
+    password: password.into(),
+    broadcaster,
+  });
+
+  Router::new()
+    .route("/", post(handle::<T, Q>))
+    .with_state(state)
+}
+
+#[tracing::instrument(name = "webhook", skip_all)]
+async fn handle<T, Q>(
+  State(state): State<Arc<WebhookState<T, Q>>>,
+  headers: HeaderMap,
+  body: Bytes,
+) -> StatusCode
+where
+  T: VoteHandler<Q>,
+  Q: DeserializeOwned + Default + Clone + Send + Sync + 'static,
+{
+  let authorization = headers
+    .get("authorization")
+    .and_then(|value| value.to_str().ok())
+    .unwrap_or_default();
+
+  if authorization != state.password {
+    warn!("incoming webhook request failed authentication");
+    return StatusCode::UNAUTHORIZED;
+  }
+
+  let event = match serde_json::from_slice::<WebhookEvent<Q>>(&body) {
+    Ok(event) => event,
+    Err(error) => {
+      trace!(body = %String::from_utf8_lossy(&body), %error, "failed to deserialize webhook body");
+      return StatusCode::BAD_REQUEST;
+    }
+  };
+
+  if let WebhookEvent::TypeSafe(known) = &event {
+    let vote = known.vote();
+    debug!(receiver_id = vote.receiver_id, voter_id = vote.voter_id, "authenticated incoming vote");
+  }
+
+  if let Some(broadcaster) = &state.broadcaster {
+    broadcaster.publish(event.clone());
+  }
+
+  state.state.voted(event).await;
+
+  StatusCode::OK
+}
@@ -0,0 +1,35 @@
+mod vote;
+
+pub use vote::*;
+
+cfg_if::cfg_if! {
+  if #[cfg(feature = "actix")] {
+    mod actix;
+
+    pub use self::actix::*;
+  }
+}
+
+cfg_if::cfg_if! {
+  if #[cfg(feature = "rocket")] {
+    mod rocket;
+
+    pub use self::rocket::*;
+  }
+}
+
+cfg_if::cfg_if! {
+  if #[cfg(feature = "axum")] {
+    mod axum;
+
+    pub use self::axum::*;
+  }
+}
+
+cfg_if::cfg_if! {
+  if #[cfg(feature = "warp")] {
+    mod warp;
+
+    pub use self::warp::*;
+  }
+}
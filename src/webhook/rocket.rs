@@ -0,0 +1,61 @@
+use super::vote::IncomingVote;
+use rocket::data::{Data, FromData, Outcome, ToByteUnit};
+use rocket::http::Status;
+use rocket::Request;
+use serde::de::DeserializeOwned;
+use tracing::trace;
+
+/// Errors that can occur while reading or parsing an incoming webhook body.
+#[derive(Debug)]
+pub enum WebhookDataError {
+  /// The request body couldn't be read off the wire.
+  Io(std::io::Error),
+
+  /// The request body didn't deserialize into a [`crate::webhook::WebhookEvent`].
+  Json(serde_json::Error),
+}
+
+impl std::fmt::Display for WebhookDataError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Io(error) => write!(f, "failed to read webhook body: {error}"),
+      Self::Json(error) => write!(f, "failed to parse webhook body: {error}"),
+    }
+  }
+}
+
+impl std::error::Error for WebhookDataError {}
+
+#[rocket::async_trait]
+impl<'r, Q> FromData<'r> for IncomingVote<Q>
+where
+  Q: DeserializeOwned + Default,
+{
+  type Error = WebhookDataError;
+
+  #[cfg_attr(docsrs, doc(cfg(feature = "rocket")))]
+  #[tracing::instrument(name = "webhook", skip_all)]
+  async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+    let authorization = req
+      .headers()
+      .get_one("authorization")
+      .unwrap_or_default()
+      .to_owned();
+
+    let body = match data.open(2.mebibytes()).into_bytes().await {
+      Ok(body) => body.into_inner(),
+      Err(error) => return Outcome::Error((Status::InternalServerError, WebhookDataError::Io(error))),
+    };
+
+    match serde_json::from_slice(&body) {
+      Ok(event) => Outcome::Success(Self {
+        authorization,
+        event,
+      }),
+      Err(error) => {
+        trace!(body = %String::from_utf8_lossy(&body), %error, "failed to deserialize webhook body");
+        Outcome::Error((Status::BadRequest, WebhookDataError::Json(error)))
+      }
+    }
+  }
+}
@@ -1,12 +1,21 @@
 use crate::snowflake;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Deserializer};
 use std::collections::HashMap;
+use tracing::{debug, trace, warn};
 
 /// A struct representing a dispatched [Top.gg](https://top.gg) bot/server vote event.
+///
+/// `Q` is the type query strings found on the vote page are parsed into. It
+/// defaults to a `HashMap<String, String>` so existing code keeps compiling,
+/// but any `Q: DeserializeOwned + Default` can be plugged in — for example a
+/// `#[derive(Deserialize, Default)]` struct matching the query parameters
+/// your own vote page links use.
 #[must_use]
 #[cfg_attr(docsrs, doc(cfg(feature = "webhook")))]
 #[derive(Clone, Debug, Deserialize)]
-pub struct Vote {
+#[serde(bound(deserialize = "Q: DeserializeOwned + Default"))]
+pub struct Vote<Q = HashMap<String, String>> {
   /// The ID of the bot/server that received a vote.
   #[serde(
     deserialize_with = "snowflake::deserialize",
@@ -28,9 +37,100 @@ pub struct Vote {
   #[serde(default, rename = "isWeekend")]
   pub is_weekend: bool,
 
-  /// Query strings found on the vote page.
+  /// Query strings found on the vote page, parsed into `Q`.
   #[serde(default, deserialize_with = "deserialize_query_string")]
-  pub query: HashMap<String, String>,
+  pub query: Q,
+}
+
+/// A vote event whose shape top.gg is known to send, fully typed.
+///
+/// Unlike [`Vote`], this distinguishes bot votes from server votes instead of
+/// aliasing them both into a single `receiver_id`, and splits test votes out
+/// into their own variant instead of a bare `is_test` flag.
+#[must_use]
+#[cfg_attr(docsrs, doc(cfg(feature = "webhook")))]
+#[derive(Clone, Debug)]
+pub enum KnownEvent<Q = HashMap<String, String>> {
+  /// A vote cast for a bot.
+  BotVote(Vote<Q>),
+
+  /// A vote cast for a server.
+  ServerVote(Vote<Q>),
+
+  /// A test vote, sent from the bot/server's owner using the webhook tester on top.gg.
+  Test(Vote<Q>),
+}
+
+impl<Q> KnownEvent<Q> {
+  /// Returns the [`Vote`] carried by this event, regardless of variant.
+  #[inline(always)]
+  pub const fn vote(&self) -> &Vote<Q> {
+    match self {
+      Self::BotVote(vote) | Self::ServerVote(vote) | Self::Test(vote) => vote,
+    }
+  }
+}
+
+/// A webhook event dispatched by [Top.gg](https://top.gg).
+///
+/// This is forward-compatible with payload shapes top.gg has not introduced
+/// yet: anything that doesn't parse into a [`KnownEvent`] is preserved as
+/// [`WebhookEvent::Dynamic`] instead of failing deserialization outright, so
+/// handlers can still observe (and log, or forward) events they don't
+/// understand.
+#[must_use]
+#[cfg_attr(docsrs, doc(cfg(feature = "webhook")))]
+#[derive(Clone, Debug)]
+pub enum WebhookEvent<Q = HashMap<String, String>> {
+  /// An event matching one of the shapes this crate knows how to parse strictly.
+  TypeSafe(KnownEvent<Q>),
+
+  /// An event that didn't match any known shape, kept as raw JSON.
+  Dynamic(serde_json::Value),
+}
+
+impl<Q> WebhookEvent<Q> {
+  /// A short, stable name for this event, suitable for logging.
+  ///
+  /// Returns `"bot_vote"`, `"server_vote"` or `"test"` for known events, and
+  /// `"dynamic"` for anything that fell back to [`WebhookEvent::Dynamic`].
+  #[inline(always)]
+  pub const fn event_name(&self) -> &'static str {
+    match self {
+      Self::TypeSafe(KnownEvent::BotVote(_)) => "bot_vote",
+      Self::TypeSafe(KnownEvent::ServerVote(_)) => "server_vote",
+      Self::TypeSafe(KnownEvent::Test(_)) => "test",
+      Self::Dynamic(_) => "dynamic",
+    }
+  }
+}
+
+impl<'de, Q> Deserialize<'de> for WebhookEvent<Q>
+where
+  Q: DeserializeOwned + Default,
+{
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let value = serde_json::Value::deserialize(deserializer)?;
+    let is_server_vote = value.get("guild").is_some();
+
+    let Ok(vote) = serde_json::from_value::<Vote<Q>>(value.clone()) else {
+      trace!(body = %value, "webhook payload did not match any known event shape, treating as dynamic");
+      return Ok(Self::Dynamic(value));
+    };
+
+    let known = if vote.is_test {
+      KnownEvent::Test(vote)
+    } else if is_server_vote {
+      KnownEvent::ServerVote(vote)
+    } else {
+      KnownEvent::BotVote(vote)
+    };
+
+    Ok(Self::TypeSafe(known))
+  }
 }
 
 #[inline(always)]
@@ -41,52 +141,48 @@ where
   String::deserialize(deserializer).map(|s| s == "test")
 }
 
-fn deserialize_query_string<'de, D>(deserializer: D) -> Result<HashMap<String, String>, D::Error>
+fn deserialize_query_string<'de, D, Q>(deserializer: D) -> Result<Q, D::Error>
 where
   D: Deserializer<'de>,
+  Q: DeserializeOwned + Default,
 {
   Ok(
     String::deserialize(deserializer)
-      .map(|s| {
-        let mut output = HashMap::new();
-
-        for mut it in s.split('&').map(|pair| pair.split('=')) {
-          if let (Some(k), Some(v)) = (it.next(), it.next()) {
-            if let Ok(v) = urlencoding::decode(v) {
-              output.insert(k.to_owned(), v.into_owned());
-            }
-          }
-        }
-
-        output
-      })
+      .ok()
+      .and_then(|s| serde_qs::from_str(&s).ok())
       .unwrap_or_default(),
   )
 }
 
 cfg_if::cfg_if! {
   if #[cfg(any(feature = "actix", feature = "rocket"))] {
-    /// A struct that represents an unauthenticated request containing a [`Vote`] data.
+    /// A struct that represents an unauthenticated request containing a [`WebhookEvent`] data.
     #[must_use]
     #[cfg_attr(docsrs, doc(cfg(any(feature = "actix", feature = "rocket"))))]
     #[derive(Clone)]
-    pub struct IncomingVote {
+    pub struct IncomingVote<Q = HashMap<String, String>> {
       pub(crate) authorization: String,
-      pub(crate) vote: Vote,
+      pub(crate) event: WebhookEvent<Q>,
     }
 
-    impl IncomingVote {
+    impl<Q> IncomingVote<Q> {
       /// Authenticates a valid password with this request.
-      /// Returns [`Some(Vote)`][`Vote`] if succeeds, otherwise `None`.
+      /// Returns [`Some(WebhookEvent)`][`WebhookEvent`] if succeeds, otherwise `None`.
       #[must_use]
-      #[inline(always)]
-      pub fn authenticate<S>(self, password: &S) -> Option<Vote>
+      #[tracing::instrument(skip_all, fields(event = self.event.event_name()))]
+      pub fn authenticate<S>(self, password: &S) -> Option<WebhookEvent<Q>>
       where
         S: AsRef<str> + ?Sized,
       {
         if self.authorization == password.as_ref() {
-          Some(self.vote)
+          if let WebhookEvent::TypeSafe(known) = &self.event {
+            let vote = known.vote();
+            debug!(receiver_id = vote.receiver_id, voter_id = vote.voter_id, "authenticated incoming vote");
+          }
+
+          Some(self.event)
         } else {
+          warn!("incoming webhook request failed authentication");
           None
         }
       }
@@ -96,9 +192,10 @@ cfg_if::cfg_if! {
 
 cfg_if::cfg_if! {
   if #[cfg(any(feature = "axum", feature = "warp"))] {
-    pub(crate) struct WebhookState<T> {
+    pub(crate) struct WebhookState<T, Q = HashMap<String, String>> {
       pub(crate) state: T,
       pub(crate) password: String,
+      pub(crate) broadcaster: Option<VoteBroadcaster<Q>>,
     }
 
     /// An async trait for adding an on-vote event handler to your application logic.
@@ -106,14 +203,115 @@ cfg_if::cfg_if! {
     /// It's described as follows (without `async_trait`'s macro expansion):
     /// ```rust,no_run
     /// #[async_trait::async_trait]
-    /// pub trait VoteHandler: Send + Sync + 'static {
-    ///   async fn voted(&self, vote: Vote);
+    /// pub trait VoteHandler<Q = HashMap<String, String>>: Send + Sync + 'static {
+    ///   async fn voted(&self, event: WebhookEvent<Q>);
     /// }
     /// ```
     #[cfg_attr(docsrs, doc(cfg(any(feature = "axum", feature = "warp"))))]
     #[async_trait::async_trait]
-    pub trait VoteHandler: Send + Sync + 'static {
-      async fn voted(&self, vote: Vote);
+    pub trait VoteHandler<Q = HashMap<String, String>>: Send + Sync + 'static {
+      async fn voted(&self, event: WebhookEvent<Q>);
+    }
+
+    /// A handle for publishing [`WebhookEvent`]s to any number of independently
+    /// subscribed receivers, alongside a [`VoteHandler`].
+    ///
+    /// Where `VoteHandler` binds a single handler to the server state,
+    /// `VoteBroadcaster` lets callers `subscribe` as many receivers as they
+    /// like (metrics, reward granting, logging, ...) and add or drop them at
+    /// runtime without rebuilding the router. The routes built with a
+    /// broadcaster still take a `VoteHandler` and call it for every event as
+    /// well.
+    #[must_use]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "axum", feature = "warp"))))]
+    #[derive(Clone)]
+    pub struct VoteBroadcaster<Q = HashMap<String, String>> {
+      sender: tokio::sync::broadcast::Sender<WebhookEvent<Q>>,
+    }
+
+    impl<Q> VoteBroadcaster<Q>
+    where
+      Q: Clone + Send + Sync + 'static,
+    {
+      /// Creates a new broadcaster, buffering up to `capacity` unreceived events per subscriber before the oldest are dropped.
+      pub fn new(capacity: usize) -> Self {
+        let (sender, _) = tokio::sync::broadcast::channel(capacity);
+
+        Self { sender }
+      }
+
+      /// Subscribes a new receiver, which observes every [`WebhookEvent`] published from this point onward.
+      #[inline(always)]
+      pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<WebhookEvent<Q>> {
+        self.sender.subscribe()
+      }
+
+      /// Publishes an event to every currently subscribed receiver.
+      #[inline(always)]
+      pub(crate) fn publish(&self, event: WebhookEvent<Q>) {
+        let _ = self.sender.send(event);
+      }
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::{KnownEvent, WebhookEvent};
+  use std::collections::HashMap;
+
+  fn parse(json: &str) -> WebhookEvent {
+    serde_json::from_str(json).expect("payload should deserialize")
+  }
+
+  #[test]
+  fn dispatches_bot_votes() {
+    let event = parse(
+      r#"{"bot":"123456789012345678","user":"876543210987654321","type":"upvote","isWeekend":false}"#,
+    );
+
+    assert_eq!(event.event_name(), "bot_vote");
+    assert!(matches!(event, WebhookEvent::TypeSafe(KnownEvent::BotVote(_))));
+  }
+
+  #[test]
+  fn dispatches_server_votes() {
+    let event = parse(r#"{"guild":"123456789012345678","user":"876543210987654321","type":"upvote"}"#);
+
+    assert_eq!(event.event_name(), "server_vote");
+    assert!(matches!(event, WebhookEvent::TypeSafe(KnownEvent::ServerVote(_))));
+  }
+
+  #[test]
+  fn dispatches_test_votes() {
+    let event = parse(r#"{"bot":"123456789012345678","user":"876543210987654321","type":"test"}"#);
+
+    assert_eq!(event.event_name(), "test");
+    assert!(matches!(event, WebhookEvent::TypeSafe(KnownEvent::Test(_))));
+  }
+
+  #[test]
+  fn falls_back_to_dynamic_for_unrecognized_shapes() {
+    let event = parse(r#"{"type":"some_future_event","payload":{"nested":true}}"#);
+
+    assert_eq!(event.event_name(), "dynamic");
+    assert!(matches!(event, WebhookEvent::Dynamic(_)));
+  }
+
+  #[test]
+  fn parses_query_strings_into_the_default_map() {
+    let event = parse(
+      r#"{"bot":"123456789012345678","user":"876543210987654321","type":"upvote","query":"ref=abc&campaign=xyz"}"#,
+    );
+
+    let WebhookEvent::TypeSafe(known) = event else {
+      panic!("expected a type-safe event");
+    };
+
+    let mut expected = HashMap::new();
+    expected.insert("ref".to_owned(), "abc".to_owned());
+    expected.insert("campaign".to_owned(), "xyz".to_owned());
+
+    assert_eq!(known.vote().query, expected);
+  }
+}
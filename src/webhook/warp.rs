@@ -0,0 +1,95 @@
+use super::vote::{VoteBroadcaster, VoteHandler, WebhookEvent, WebhookState};
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+use tracing::{debug, trace, warn};
+use warp::{http::StatusCode, Filter, Reply};
+
+/// Builds a [`warp::Filter`] that authenticates incoming [Top.gg](https://top.gg)
+/// webhook requests and dispatches each one to `handler`'s [`VoteHandler::voted`].
+#[must_use]
+#[cfg_attr(docsrs, doc(cfg(feature = "warp")))]
+pub fn webhook<T, Q>(
+  password: impl Into<String>,
+  handler: T,
+) -> impl Filter<Extract = (impl Reply,), Error = warp::Rejection> + Clone
+where
+  T: VoteHandler<Q>,
+  Q: DeserializeOwned + Default + Clone + Send + Sync + 'static,
+{
+  build_filter(password, handler, None)
+}
+
+/// Like [`webhook`], but also publishes every authenticated vote to `broadcaster`'s
+/// subscribers, in addition to calling `handler`'s [`VoteHandler::voted`].
+#[must_use]
+#[cfg_attr(docsrs, doc(cfg(feature = "warp")))]
+pub fn webhook_with_broadcaster<T, Q>(
+  password: impl Into<String>,
+  handler: T,
+  broadcaster: VoteBroadcaster<Q>,
+) -> impl Filter<Extract = (impl Reply,), Error = warp::Rejection> + Clone
+where
+  T: VoteHandler<Q>,
+  Q: DeserializeOwned + Default + Clone + Send + Sync + 'static,
+{
+  build_filter(password, handler, Some(broadcaster))
+}
+
+fn build_filter<T, Q>(
+  password: impl Into<String>,
+  handler: T,
+  broadcaster: Option<VoteBroadcaster<Q>>,
+) -> impl Filter<Extract = (impl Reply,), Error = warp::Rejection> + Clone
+where
+  T: VoteHandler<Q>,
+  Q: DeserializeOwned + Default + Clone + Send + Sync + 'static,
+{
+  let state = Arc::new(WebhookState {
+    state: handler,
+    password: password.into(),
+    broadcaster,
+  });
+
+  warp::post()
+    .and(warp::header::optional::<String>("authorization"))
+    .and(warp::body::bytes())
+    .and(warp::any().map(move || Arc::clone(&state)))
+    .and_then(handle)
+}
+
+#[tracing::instrument(name = "webhook", skip_all)]
+async fn handle<T, Q>(
+  authorization: Option<String>,
+  body: bytes::Bytes,
+  state: Arc<WebhookState<T, Q>>,
+) -> Result<impl Reply, warp::Rejection>
+where
+  T: VoteHandler<Q>,
+  Q: DeserializeOwned + Default + Clone + Send + Sync + 'static,
+{
+  if authorization.as_deref() != Some(state.password.as_str()) {
+    warn!("incoming webhook request failed authentication");
+    return Ok(StatusCode::UNAUTHORIZED);
+  }
+
+  let event = match serde_json::from_slice::<WebhookEvent<Q>>(&body) {
+    Ok(event) => event,
+    Err(error) => {
+      trace!(body = %String::from_utf8_lossy(&body), %error, "failed to deserialize webhook body");
+      return Ok(StatusCode::BAD_REQUEST);
+    }
+  };
+
+  if let WebhookEvent::TypeSafe(known) = &event {
+    let vote = known.vote();
+    debug!(receiver_id = vote.receiver_id, voter_id = vote.voter_id, "authenticated incoming vote");
+  }
+
+  if let Some(broadcaster) = &state.broadcaster {
+    broadcaster.publish(event.clone());
+  }
+
+  state.state.voted(event).await;
+
+  Ok(StatusCode::OK)
+}